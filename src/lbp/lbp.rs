@@ -0,0 +1,219 @@
+use crate::transcendental::pow;
+use crate::types::{Balance, FixedBalance};
+use crate::MathError;
+use crate::MathError::{Overflow, ZeroDuration, ZeroInReserve, ZeroOutWeight};
+use num_traits::{CheckedSub, Zero};
+use primitive_types::U256;
+
+/// Converts a `numerator / denominator` ratio into a `FixedBalance`, going through `U256` so the
+/// `numerator` can be shifted by the full fractional width without overflowing `u128` first.
+fn to_fixed_ratio(numerator: Balance, denominator: Balance) -> Result<FixedBalance, MathError> {
+    to_fixed_ratio_u256(U256::from(numerator), U256::from(denominator))
+}
+
+/// as [`to_fixed_ratio`], but takes its operands pre-widened to `U256`. Used where the
+/// denominator is itself the sum of two `Balance`s and so may not fit back into a `u128`.
+fn to_fixed_ratio_u256(numerator: U256, denominator: U256) -> Result<FixedBalance, MathError> {
+    if denominator.is_zero() {
+        return Err(Overflow);
+    };
+
+    let shifted = numerator << FixedBalance::FRAC_NBITS;
+    let bits = shifted.checked_div(denominator).ok_or(Overflow)?;
+
+    Ok(FixedBalance::from_bits(bits.try_into().map_err(|_| Overflow)?))
+}
+
+/// `fraction * balance`, rounded to the nearest integer. Goes through `U256` rather than
+/// `FixedBalance::checked_mul_int` because `balance` routinely exceeds what `FixedBalance`'s own
+/// (small) integer range can hold, even though the product fits in a `Balance`.
+fn fixed_mul_balance(fraction: FixedBalance, balance: Balance) -> Result<Balance, MathError> {
+    let product = U256::from(fraction.to_bits()).checked_mul(U256::from(balance)).ok_or(Overflow)?;
+    let half_ulp = U256::from(1u128) << (FixedBalance::FRAC_NBITS - 1);
+    let rounded = product.checked_add(half_ulp).ok_or(Overflow)? >> FixedBalance::FRAC_NBITS;
+
+    rounded.try_into().map_err(|_| Overflow)
+}
+
+/// spot price of `amount` given reserves and weights of two assets in an LBP pool
+///
+/// `spot_price = amount * buy_reserve * sell_weight / (sell_reserve * buy_weight)`
+pub fn calculate_spot_price(
+    sell_reserve: Balance,
+    buy_reserve: Balance,
+    sell_weight: Balance,
+    buy_weight: Balance,
+    amount: Balance,
+) -> Result<Balance, MathError> {
+    if sell_reserve.is_zero() {
+        return Err(ZeroInReserve);
+    };
+
+    amount
+        .checked_mul(buy_reserve)
+        .ok_or(Overflow)?
+        .checked_mul(sell_weight)
+        .ok_or(Overflow)?
+        .checked_div(sell_reserve.checked_mul(buy_weight).ok_or(Overflow)?)
+        .ok_or(Overflow)
+}
+
+/// amount of asset received by selling `amount` of the other asset into an LBP pool, using the
+/// weighted constant-product invariant:
+///
+/// `amount_out = buy_reserve * (1 - (sell_reserve / (sell_reserve + amount))^(sell_weight / buy_weight))`
+pub fn calculate_out_given_in(
+    sell_reserve: Balance,
+    buy_reserve: Balance,
+    sell_weight: Balance,
+    buy_weight: Balance,
+    amount: Balance,
+) -> Result<Balance, MathError> {
+    if buy_weight.is_zero() {
+        return Err(ZeroOutWeight);
+    };
+
+    let denominator = U256::from(sell_reserve) + U256::from(amount);
+    let base = to_fixed_ratio_u256(U256::from(sell_reserve), denominator)?;
+    let exponent = to_fixed_ratio(sell_weight, buy_weight)?;
+
+    let result: FixedBalance = pow(base, exponent).map_err(|_| Overflow)?;
+    let complement = FixedBalance::from_num(1).checked_sub(result).ok_or(Overflow)?;
+
+    fixed_mul_balance(complement, buy_reserve)
+}
+
+/// amount of asset that must be sold into an LBP pool to receive `amount` of the other asset,
+/// using the weighted constant-product invariant:
+///
+/// `amount_in = sell_reserve * ((buy_reserve / (buy_reserve - amount))^(buy_weight / sell_weight) - 1)`
+pub fn calculate_in_given_out(
+    sell_reserve: Balance,
+    buy_reserve: Balance,
+    sell_weight: Balance,
+    buy_weight: Balance,
+    amount: Balance,
+) -> Result<Balance, MathError> {
+    let denominator = buy_reserve.checked_sub(amount).ok_or(Overflow)?;
+    let base = to_fixed_ratio(buy_reserve, denominator)?;
+    let exponent = to_fixed_ratio(buy_weight, sell_weight)?;
+
+    let result: FixedBalance = pow(base, exponent).map_err(|_| Overflow)?;
+    let factor = result.checked_sub(FixedBalance::from_num(1)).ok_or(Overflow)?;
+
+    fixed_mul_balance(factor, sell_reserve)
+}
+
+/// linear weight curve: a straight-line ramp from `start_weight` at `start` to `end_weight` at
+/// `end`. Kept as a thin wrapper over [`calculate_weights`] for backward compatibility.
+pub fn calculate_linear_weights<BlockNumber>(
+    start: BlockNumber,
+    end: BlockNumber,
+    start_weight: Balance,
+    end_weight: Balance,
+    at: BlockNumber,
+) -> Result<Balance, MathError>
+where
+    BlockNumber: Copy + PartialOrd + CheckedSub + TryInto<u32>,
+{
+    calculate_weights(start, end, start_weight, end_weight, at, WeightCurve::Linear)
+}
+
+/// shape of the weight transition over an LBP sale's `[start, end]` interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeightCurve {
+    /// straight-line ramp between `start_weight` and `end_weight`.
+    Linear,
+    /// `start_weight * (end_weight / start_weight) ^ t`, front- or back-loading the shift
+    /// depending on whether `end_weight` is above or below `start_weight`.
+    Exponential,
+    /// holds the weight constant across `steps` equally-sized plateaus, jumping to the next
+    /// plateau's linearly-interpolated value once its elapsed fraction is reached.
+    Stepwise { steps: u32 },
+}
+
+/// weight at block `at` of an LBP sale running from `start` to `end`, following `curve`.
+pub fn calculate_weights<BlockNumber>(
+    start: BlockNumber,
+    end: BlockNumber,
+    start_weight: Balance,
+    end_weight: Balance,
+    at: BlockNumber,
+    curve: WeightCurve,
+) -> Result<Balance, MathError>
+where
+    BlockNumber: Copy + PartialOrd + CheckedSub + TryInto<u32>,
+{
+    let duration: u32 = end
+        .checked_sub(&start)
+        .ok_or(Overflow)?
+        .try_into()
+        .map_err(|_| Overflow)?;
+    if duration.is_zero() {
+        return Err(ZeroDuration);
+    };
+
+    let elapsed: u32 = at
+        .checked_sub(&start)
+        .ok_or(Overflow)?
+        .try_into()
+        .map_err(|_| Overflow)?;
+    if elapsed > duration {
+        return Err(Overflow);
+    };
+
+    match curve {
+        WeightCurve::Linear => weight_at(start_weight, end_weight, elapsed, duration),
+        WeightCurve::Exponential => weight_at_exponential(start_weight, end_weight, elapsed, duration),
+        WeightCurve::Stepwise { steps } => {
+            if steps.is_zero() {
+                return Err(Overflow);
+            };
+            let plateau = elapsed.checked_mul(steps).ok_or(Overflow)? / duration;
+            let plateau_elapsed = plateau.checked_mul(duration).ok_or(Overflow)? / steps;
+            weight_at(start_weight, end_weight, plateau_elapsed, duration)
+        }
+    }
+}
+
+/// linear interpolation between `start_weight` and `end_weight` at `elapsed / duration`.
+fn weight_at(start_weight: Balance, end_weight: Balance, elapsed: u32, duration: u32) -> Result<Balance, MathError> {
+    if end_weight >= start_weight {
+        let delta = (end_weight - start_weight)
+            .checked_mul(elapsed as Balance)
+            .ok_or(Overflow)?
+            .checked_div(duration as Balance)
+            .ok_or(Overflow)?;
+        start_weight.checked_add(delta).ok_or(Overflow)
+    } else {
+        let delta = (start_weight - end_weight)
+            .checked_mul(elapsed as Balance)
+            .ok_or(Overflow)?
+            .checked_div(duration as Balance)
+            .ok_or(Overflow)?;
+        start_weight.checked_sub(delta).ok_or(Overflow)
+    }
+}
+
+/// `start_weight * (end_weight / start_weight) ^ (elapsed / duration)`, computed with the
+/// module's fixed-point `pow` so large ratios stay precise instead of truncating early.
+fn weight_at_exponential(
+    start_weight: Balance,
+    end_weight: Balance,
+    elapsed: u32,
+    duration: u32,
+) -> Result<Balance, MathError> {
+    if start_weight.is_zero() {
+        return Err(Overflow);
+    };
+    if elapsed.is_zero() {
+        return Ok(start_weight);
+    };
+
+    let base = to_fixed_ratio(end_weight, start_weight)?;
+    let t = to_fixed_ratio(elapsed as Balance, duration as Balance)?;
+
+    let factor: FixedBalance = pow(base, t).map_err(|_| Overflow)?;
+
+    fixed_mul_balance(factor, start_weight)
+}
@@ -0,0 +1,5 @@
+#[allow(clippy::module_inception)]
+pub mod lbp;
+
+#[cfg(test)]
+mod tests;
@@ -1,4 +1,5 @@
 use crate::lbp::lbp;
+use crate::lbp::lbp::WeightCurve;
 
 use crate::MathError::{Overflow, ZeroDuration, ZeroInReserve, ZeroOutWeight};
 
@@ -71,7 +72,7 @@ fn in_given_out_should_work() {
             50 * prec,
             100 * prec,
             prec,
-            Ok(10803324099600),
+            Ok(10803324099723),
             "Easy case",
         ),
         (
@@ -80,7 +81,7 @@ fn in_given_out_should_work() {
             100 * prec,
             50 * prec,
             prec,
-            Ok(2597864120100),
+            Ok(2597835208515),
             "Easy case",
         ),
         (
@@ -89,10 +90,19 @@ fn in_given_out_should_work() {
             100 * prec,
             1200 * prec,
             2 * prec,
-            Ok(7336295198400),
+            Ok(7336295198683),
             "Easy case",
         ),
         (0, 0, 0, 0, 100, Err(Overflow), "Zero reserves and weights"),
+        (
+            100,
+            1000,
+            1,
+            20,
+            500,
+            Err(Overflow),
+            "Large weight skew overflows pow's result range",
+        ),
     ];
 
     for case in cases {
@@ -239,4 +249,148 @@ fn linear_weights_should_work() {
             case.6
         );
     }
+}
+
+#[test]
+fn exponential_weights_should_work() {
+    let cases = vec![
+        (100u32, 200u32, 1_000u128, 2_000u128, 170u32, Ok(1_625), "Easy case"),
+        (
+            100u32,
+            200u32,
+            2_000u128,
+            1_000u128,
+            170u32,
+            Ok(1_231),
+            "Easy decreasing case",
+        ),
+        (
+            100u32,
+            200u32,
+            1_000u128,
+            2_000u128,
+            100u32,
+            Ok(1_000),
+            "Initial weight",
+        ),
+        (100u32, 200u32, 1_000u128, 2_000u128, 200u32, Ok(2_000), "Final weight"),
+        (
+            100u32,
+            200u32,
+            1_000u128,
+            2_000u128,
+            150u32,
+            Ok(1_414),
+            "Halfway weight is the geometric mean",
+        ),
+        (
+            200u32,
+            100u32,
+            1_000u128,
+            2_000u128,
+            170u32,
+            Err(Overflow),
+            "Invalid interval",
+        ),
+        (
+            100u32,
+            100u32,
+            1_000u128,
+            2_000u128,
+            100u32,
+            Err(ZeroDuration),
+            "Invalid interval",
+        ),
+    ];
+
+    for case in cases {
+        assert_eq!(
+            lbp::calculate_weights(case.0, case.1, case.2, case.3, case.4, WeightCurve::Exponential),
+            case.5,
+            "{}",
+            case.6
+        );
+    }
+}
+
+#[test]
+fn stepwise_weights_should_work() {
+    let cases = vec![
+        (
+            100u32,
+            200u32,
+            1_000u128,
+            2_000u128,
+            170u32,
+            4u32,
+            Ok(1_500),
+            "Third plateau",
+        ),
+        (
+            100u32,
+            200u32,
+            1_000u128,
+            2_000u128,
+            100u32,
+            4u32,
+            Ok(1_000),
+            "Initial plateau",
+        ),
+        (
+            100u32,
+            200u32,
+            1_000u128,
+            2_000u128,
+            199u32,
+            4u32,
+            Ok(1_750),
+            "Last plateau before the end",
+        ),
+        (
+            100u32,
+            200u32,
+            1_000u128,
+            2_000u128,
+            200u32,
+            4u32,
+            Ok(2_000),
+            "Final weight",
+        ),
+        (
+            100u32,
+            200u32,
+            2_000u128,
+            1_000u128,
+            170u32,
+            5u32,
+            Ok(1_400),
+            "Decreasing case",
+        ),
+        (
+            100u32,
+            200u32,
+            1_000u128,
+            2_000u128,
+            150u32,
+            0u32,
+            Err(Overflow),
+            "Zero steps",
+        ),
+    ];
+
+    for case in cases {
+        assert_eq!(
+            lbp::calculate_weights(
+                case.0,
+                case.1,
+                case.2,
+                case.3,
+                case.4,
+                WeightCurve::Stepwise { steps: case.5 }
+            ),
+            case.6,
+            "{}",
+            case.7
+        );
+    }
 }
\ No newline at end of file
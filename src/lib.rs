@@ -0,0 +1,18 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+#[macro_use]
+extern crate approx;
+
+pub mod lbp;
+pub mod transcendental;
+pub mod types;
+
+/// Errors shared by the fixed-point math helpers across this crate's pool modules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathError {
+    ZeroDuration,
+    ZeroInReserve,
+    ZeroOutWeight,
+    Overflow,
+}
@@ -1,9 +1,7 @@
 #![allow(clippy::result_unit_err)]
 
 use core::convert::From;
-use core::ops::{AddAssign, BitOrAssign, ShlAssign, Shr, ShrAssign};
 use fixed::traits::FixedUnsigned;
-use fixed::traits::ToFixed;
 
 /// right-shift with rounding
 fn rs<T>(operand: T) -> T
@@ -14,36 +12,40 @@ where
     (operand >> 1) + (operand & lsb)
 }
 
+/// ln(2) at full `D` precision, used by `exp`/`exp2`/`ln` for argument reduction and
+/// change-of-base so the only hardcoded transcendental constant any of them needs is this one.
+fn ln2<D>() -> Result<D, ()>
+where
+    D: FixedUnsigned,
+{
+    D::from_str("0.693147180559945309417232121458176568075").map_err(|_| ())
+}
+
 /// base 2 logarithm assuming self >=1
-fn log2_inner<S, D>(operand: S) -> D
+fn log2_inner<S, D>(operand: S) -> Result<D, ()>
 where
     S: FixedUnsigned + PartialOrd<D>,
     D: FixedUnsigned,
-    D::Bits: Copy + ToFixed + AddAssign + BitOrAssign + ShlAssign,
 {
     let two = D::from_num(2);
     let mut x = operand;
-    let mut result = D::from_num(0).to_bits();
-    let lsb = (D::from_num(1) >> D::FRAC_NBITS).to_bits();
+    let mut result = D::from_num(0);
+    let lsb = D::from_num(1) >> D::FRAC_NBITS;
 
     while x >= two {
-        result += lsb;
+        result = result.checked_add(lsb).ok_or(())?;
         x = rs(x);
     }
 
-    if x == D::from_num(1) {
-        return D::from_num(result);
-    };
-
     for _i in (0..D::FRAC_NBITS).rev() {
-        x *= x;
-        result <<= lsb;
+        x = x.checked_mul(x).ok_or(())?;
+        result = result.checked_mul(two).ok_or(())?;
         if x >= two {
-            result |= lsb;
+            result = result.checked_add(lsb).ok_or(())?;
             x = rs(x);
         }
     }
-    D::from_bits(result)
+    Ok(result)
 }
 
 /// base 2 logarithm
@@ -53,7 +55,6 @@ pub fn log2<S, D>(operand: S) -> Result<(D, bool), ()>
 where
     S: FixedUnsigned,
     D: FixedUnsigned + From<S>,
-    D::Bits: Copy + ToFixed + AddAssign + BitOrAssign + ShlAssign,
 {
     if operand <= S::from_num(0) {
         return Err(());
@@ -61,10 +62,10 @@ where
 
     let operand = D::from(operand);
     if operand < D::from_num(1) {
-        let inverse = D::from_num(1).checked_div(operand).unwrap();
-        return Ok((log2_inner::<D, D>(inverse), true));
+        let inverse = D::from_num(1).checked_div(operand).ok_or(())?;
+        return Ok((log2_inner::<D, D>(inverse)?, true));
     };
-    Ok((log2_inner::<D, D>(operand), false))
+    Ok((log2_inner::<D, D>(operand)?, false))
 }
 
 /// natural logarithm
@@ -73,12 +74,74 @@ pub fn ln<S, D>(operand: S) -> Result<(D, bool), ()>
 where
     S: FixedUnsigned,
     D: FixedUnsigned + From<S>,
-    D::Bits: Copy + ToFixed + AddAssign + BitOrAssign + ShlAssign,
-    S::Bits: Copy + ToFixed + AddAssign + BitOrAssign + ShrAssign + Shr,
 {
-    let log2_e = S::from_str("1.442695").map_err(|_| ())?;
     let log_result = log2::<S, D>(operand)?;
-    Ok((log_result.0 / D::from(log2_e), log_result.1))
+    let value = log_result.0.checked_mul(ln2()?).ok_or(())?;
+    Ok((value, log_result.1))
+}
+
+/// logarithm of `operand` to an arbitrary `base`, computed via the change-of-base identity
+/// `log_b(x) = log2(x) / log2(b)` so the only transcendental primitive needed is `log2` itself.
+///
+/// Returns tuple(D,bool) where bool indicates whether D is negative. This happens when operand is < 1.
+/// `base` must be greater than 1.
+pub fn logn<S, D>(operand: S, base: S) -> Result<(D, bool), ()>
+where
+    S: FixedUnsigned,
+    D: FixedUnsigned + From<S>,
+{
+    let (log_base, base_neg) = log2::<S, D>(base)?;
+    if base_neg || log_base.is_zero() {
+        return Err(());
+    };
+
+    let (log_operand, operand_neg) = log2::<S, D>(operand)?;
+    let value = log_operand.checked_div(log_base).ok_or(())?;
+    Ok((value, operand_neg))
+}
+
+/// base 10 logarithm
+///
+/// Returns tuple(D,bool) where bool indicates whether D is negative. This happens when operand is < 1.
+pub fn log10<S, D>(operand: S) -> Result<(D, bool), ()>
+where
+    S: FixedUnsigned,
+    D: FixedUnsigned + From<S>,
+{
+    logn::<S, D>(operand, S::from_num(10))
+}
+
+/// left-shifts `operand` by `n` bits, failing if the result doesn't fit `D`'s integer range.
+/// `Fixed::checked_shl` only validates the shift *amount* (like a primitive integer shift) and
+/// happily returns wrapped bits once the shifted value overflows, so the shift is verified here
+/// by checking it round-trips back through an equivalent right-shift.
+fn checked_shl_exact<D>(operand: D, n: u32) -> Result<D, ()>
+where
+    D: FixedUnsigned,
+{
+    let result = operand.checked_shl(n).ok_or(())?;
+    if result.checked_shr(n) != Some(operand) {
+        return Err(());
+    }
+    Ok(result)
+}
+
+/// Taylor series approximation of `e^x`, without any range reduction. Only accurate for small
+/// `x` (roughly `|x| <= ln(2)/2`); callers are expected to reduce the argument first.
+fn exp_taylor<D>(operand: D) -> Result<D, ()>
+where
+    D: FixedUnsigned,
+{
+    let mut result = operand + D::from_num(1);
+    let mut term = operand;
+
+    result = (2..D::FRAC_NBITS).try_fold(result, |acc, i| -> Result<D, ()> {
+        term = term.checked_mul(operand).ok_or(())?;
+        term = term.checked_div(D::from_num(i)).ok_or(())?;
+        acc.checked_add(term).ok_or(())
+    })?;
+
+    Ok(result)
 }
 
 /// exponential function e^(operand)
@@ -91,21 +154,84 @@ where
     if operand.is_zero() {
         return Ok(D::from_num(1));
     };
-    if operand == S::from_num(1) {
+    if operand == S::from_num(1) && !neg {
         //TODO: make this as const somewhere
         let e = S::from_str("2.718281828459045235360287471352662497757").map_err(|_| ())?;
         return Ok(D::from(e));
     };
 
     let operand = D::from(operand);
-    let mut result = operand + D::from_num(1);
-    let mut term = operand;
 
-    result = (2..D::FRAC_NBITS).try_fold(result, |acc, i| -> Result<D, ()> {
-        term = term.checked_mul(operand).ok_or(())?;
-        term = term.checked_div(D::from_num(i)).ok_or(())?;
-        acc.checked_add(term).ok_or(())
-    })?;
+    // Range-reduce `operand = n * ln2 + r` with `|r| <= ln2/2`, so `exp_taylor` always runs on a
+    // small, fast-converging argument no matter how large `operand` is. `e^operand` is then
+    // reassembled as `e^r * 2^n`, which for fixed-point numbers is just a left shift by `n` bits.
+    let ln2 = ln2::<D>()?;
+    let half_ln2 = rs(ln2);
+
+    let mut n: u32 = operand.checked_div(ln2).ok_or(())?.int().checked_to_num().ok_or(())?;
+    let mut r = operand.checked_sub(D::from_num(n).checked_mul(ln2).ok_or(())?).ok_or(())?;
+
+    let r_neg = if r > half_ln2 {
+        n = n.checked_add(1).ok_or(())?;
+        r = ln2.checked_sub(r).ok_or(())?;
+        true
+    } else {
+        false
+    };
+
+    let exp_r = exp_taylor(r)?;
+    let exp_r = if r_neg {
+        D::from_num(1).checked_div(exp_r).ok_or(())?
+    } else {
+        exp_r
+    };
+
+    let mut result = checked_shl_exact(exp_r, n)?;
+
+    if neg {
+        result = D::from_num(1).checked_div(result).ok_or(())?;
+    }
+
+    Ok(result)
+}
+
+/// binary exponential function 2^(operand)
+/// neg - bool indicates that operand is negative value.
+pub fn exp2<S, D>(operand: S, neg: bool) -> Result<D, ()>
+where
+    S: FixedUnsigned + PartialOrd<D>,
+    D: FixedUnsigned + PartialOrd<S> + From<S>,
+{
+    if operand.is_zero() {
+        return Ok(D::from_num(1));
+    };
+
+    let operand = D::from(operand);
+
+    // Range-reduce `operand = n + f` with `n` the nearest integer and `|f| <= 1/2`, so the
+    // fractional part always stays small. `2^operand` is then `2^f * 2^n`, and since
+    // `2^f = e^(f*ln2)`, `2^f` can reuse the same Taylor series as `exp`.
+    let half = rs(D::from_num(1));
+
+    let mut n: u32 = operand.int().checked_to_num().ok_or(())?;
+    let mut f = operand.checked_sub(D::from_num(n)).ok_or(())?;
+
+    let f_neg = if f > half {
+        n = n.checked_add(1).ok_or(())?;
+        f = D::from_num(1).checked_sub(f).ok_or(())?;
+        true
+    } else {
+        false
+    };
+
+    let pow2_f = exp_taylor(f.checked_mul(ln2()?).ok_or(())?)?;
+    let pow2_f = if f_neg {
+        D::from_num(1).checked_div(pow2_f).ok_or(())?
+    } else {
+        pow2_f
+    };
+
+    let mut result = checked_shl_exact(pow2_f, n)?;
 
     if neg {
         result = D::from_num(1).checked_div(result).ok_or(())?;
@@ -118,8 +244,6 @@ pub fn pow<S, D>(operand: S, exponent: S) -> Result<D, ()>
 where
     S: FixedUnsigned + PartialOrd<D>,
     D: FixedUnsigned + From<S>,
-    D::Bits: Copy + ToFixed + AddAssign + BitOrAssign + ShlAssign,
-    S::Bits: Copy + ToFixed + AddAssign + BitOrAssign + ShlAssign + Shr + ShrAssign,
 {
     if operand.is_zero() {
         return Ok(D::from_num(0));
@@ -131,10 +255,10 @@ where
         return Ok(D::from(operand));
     };
 
-    let (r, neg) = ln::<S, D>(operand)?;
+    let (r, neg) = log2::<S, D>(operand)?;
 
     let r: D = r.checked_mul(exponent.into()).ok_or(())?;
-    let r: D = exp(r, neg)?;
+    let r: D = exp2(r, neg)?;
 
     let (result, oflw) = r.overflowing_to_num::<D>();
     if oflw {
@@ -165,14 +289,72 @@ where
     r.ok_or(())
 }
 
+/// square root via Newton's method: `y = (y + x/y) / 2`, which converges quadratically once `y`
+/// is within the right order of magnitude of `sqrt(x)`.
+pub fn sqrt<S, D>(operand: S) -> Result<D, ()>
+where
+    S: FixedUnsigned,
+    D: FixedUnsigned + From<S>,
+{
+    if operand.is_zero() {
+        return Ok(D::from_num(0));
+    };
+
+    let operand = D::from(operand);
+    if operand == D::from_num(1) {
+        return Ok(D::from_num(1));
+    };
+
+    // initial estimate: halve the position of the highest set bit, i.e. if x is close to 2^k,
+    // start from 2^(k/2).
+    let mut y = D::from_num(1);
+    let mut hi = operand.highest_one();
+    while hi > D::from_num(1) {
+        hi = rs(rs(hi));
+        y = y.checked_mul(D::from_num(2)).ok_or(())?;
+    }
+
+    let lsb = D::from_num(1) >> D::FRAC_NBITS;
+    for _ in 0..D::FRAC_NBITS * 2 {
+        let next = rs(y.checked_add(operand.checked_div(y).ok_or(())?).ok_or(())?);
+        let diff = if next > y { next - y } else { y - next };
+        y = next;
+        if diff <= lsb {
+            break;
+        }
+    }
+
+    Ok(y)
+}
+
+/// nth root, computed as `2^(log2(x)/n)`.
+pub fn nth_root<S, D>(operand: S, n: u32) -> Result<D, ()>
+where
+    S: FixedUnsigned + PartialOrd<D>,
+    D: FixedUnsigned + PartialOrd<S> + From<S>,
+{
+    if n == 0 {
+        return Err(());
+    };
+    if operand.is_zero() {
+        return Ok(D::from_num(0));
+    };
+    if n == 1 {
+        return Ok(D::from(operand));
+    };
+
+    let (log, neg) = log2::<S, D>(operand)?;
+    let exponent = log.checked_div(D::from_num(n)).ok_or(())?;
+    exp2(exponent, neg)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::types::FixedBalance;
-    use core::str::FromStr;
     use fixed::traits::LossyInto;
     use fixed::types::U64F64;
 
-    use super::{exp, log2, pow, powi};
+    use super::{exp, exp2, log10, log2, logn, nth_root, pow, powi, sqrt};
 
     #[test]
     fn exp_works() {
@@ -189,7 +371,7 @@ mod tests {
         assert_eq!(exp::<S, D>(one, false), Ok(D::from_num(e)));
         assert_eq!(
             exp::<S, D>(two, false),
-            Ok(D::from_str("7.3890560989306502265").unwrap())
+            Ok(D::from_str("7.38905609893065022913").unwrap())
         );
         assert_eq!(
             exp::<S, D>(two, true),
@@ -197,6 +379,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn exp_with_large_operand_should_work() {
+        // Regression test for the range-reduced `exp`: before argument reduction was added,
+        // the raw Taylor series either lost most of its precision or overflowed `term` for
+        // operands this large.
+        type S = U64F64;
+        type D = U64F64;
+
+        let ten = S::from_num(10);
+        let twenty = S::from_num(20);
+
+        assert_eq!(
+            exp::<S, D>(ten, false),
+            Ok(D::from_str("22026.4657948067165058603").unwrap())
+        );
+        assert_eq!(
+            exp::<S, D>(ten, true),
+            Ok(D::from_str("0.00004539992976248483").unwrap())
+        );
+        assert_eq!(
+            exp::<S, D>(twenty, false),
+            Ok(D::from_str("485165195.40979027788853272796").unwrap())
+        );
+    }
+
+    #[test]
+    fn exp_with_operand_overflowing_result_is_err_not_silently_wrong() {
+        // Regression test: `checked_shl` only validates the shift amount, not whether the
+        // shifted value still fits `D`'s integer range, so `n * ln2 <= 64` (a legal shift
+        // amount for `U64F64`) used to silently wrap instead of erroring. e^80 needs ~116 bits
+        // to represent, far beyond `U64F64`'s 64 integer bits.
+        type S = U64F64;
+        type D = U64F64;
+
+        assert_eq!(exp::<S, D>(S::from_num(80), false), Err(()));
+    }
+
+    #[test]
+    fn exp_with_operand_too_large_for_n_is_err_not_panic() {
+        // Regression test for panic-freedom: the range reduction's `operand / ln2` can exceed
+        // `u32::MAX` long before the final `checked_shl` would reject it, so the conversion to
+        // `n` must itself be checked instead of panicking via `to_num()`.
+        type S = FixedBalance;
+        type D = FixedBalance;
+
+        assert_eq!(exp::<S, D>(S::from_num(181706), false), Err(()));
+    }
+
     #[test]
     fn log2_works() {
         type S = U64F64;
@@ -215,6 +445,91 @@ mod tests {
         assert_eq!(log2(S::from_num(1.0 / 0.5)), Ok((D::from_num(one), false)));
     }
 
+    #[test]
+    fn log2_reciprocal_overflow_is_err_not_panic() {
+        // Regression test for panic-freedom under `no_std`: with only 2 integer bits, `D` can't
+        // represent the reciprocal of small operands (e.g. 1/0.25 == 4, but `D::MAX` is
+        // 3.984375), so the old `.unwrap()` on that division would abort instead of returning
+        // `Err(())`.
+        type S = fixed::FixedU8<fixed::types::extra::U6>;
+        type D = fixed::FixedU8<fixed::types::extra::U6>;
+
+        assert_eq!(log2::<S, D>(S::from_num(0.25)), Err(()));
+        assert_eq!(log2::<S, D>(S::from_num(0.015625)), Err(()));
+    }
+
+    #[test]
+    fn log10_works() {
+        type S = U64F64;
+        type D = U64F64;
+
+        assert_eq!(log10::<S, D>(S::from_num(0)), Err(()));
+
+        assert_eq!(log10(S::from_num(1)), Ok((D::from_num(0), false)));
+        assert_eq!(log10(S::from_num(100)), Ok((D::from_num(2), false)));
+
+        let result: f64 = log10::<S, D>(S::from_num(0.01)).unwrap().0.lossy_into();
+        assert_relative_eq!(result, 2.0, epsilon = 1.0e-12);
+        assert!(log10::<S, D>(S::from_num(0.01)).unwrap().1);
+    }
+
+    #[test]
+    fn logn_works() {
+        type S = U64F64;
+        type D = U64F64;
+
+        assert_eq!(logn::<S, D>(S::from_num(1), S::from_num(0)), Err(()));
+        assert_eq!(logn::<S, D>(S::from_num(1), S::from_num(1)), Err(()));
+
+        assert_eq!(logn(S::from_num(8), S::from_num(2)), Ok((D::from_num(3), false)));
+        assert_eq!(logn(S::from_num(1), S::from_num(2)), Ok((D::from_num(0), false)));
+
+        let result: f64 = logn::<S, D>(S::from_num(1.0 / 8.0), S::from_num(2))
+            .unwrap()
+            .0
+            .lossy_into();
+        assert_relative_eq!(result, 3.0, epsilon = 1.0e-12);
+    }
+
+    #[test]
+    fn exp2_works() {
+        type S = U64F64;
+        type D = U64F64;
+
+        let zero = S::from_num(0);
+        let half = S::from_num(0.5);
+        let ten = S::from_num(10);
+
+        assert_eq!(exp2::<S, D>(zero, false), Ok(D::from_num(1)));
+        assert_eq!(exp2::<S, D>(ten, false), Ok(D::from_num(1024)));
+        assert_eq!(exp2::<S, D>(ten, true), Ok(D::from_num(1) / D::from_num(1024)));
+        assert_eq!(
+            exp2::<S, D>(half, false),
+            Ok(D::from_str("1.4142135623730950485").unwrap())
+        );
+    }
+
+    #[test]
+    fn exp2_with_operand_too_large_for_n_is_err_not_panic() {
+        // Regression test for panic-freedom: same class of bug as `exp`'s `n` conversion, since
+        // `exp2` range-reduces by splitting off the integer part of `operand` directly.
+        type S = U64F64;
+        type D = U64F64;
+
+        assert_eq!(exp2::<S, D>(S::from_num(5_000_000_000u64), false), Err(()));
+    }
+
+    #[test]
+    fn exp2_with_operand_overflowing_result_is_err_not_silently_wrong() {
+        // Regression test: same unverified-shift defect as `exp` (`checked_shl` only checks the
+        // shift amount), reachable here too since `exp2` reassembles its result the same way.
+        // 2^100 needs 100 integer bits, far beyond `U64F64`'s 64.
+        type S = U64F64;
+        type D = U64F64;
+
+        assert_eq!(exp2::<S, D>(S::from_num(100), false), Err(()));
+    }
+
     #[test]
     fn powi_works() {
         type S = U64F64;
@@ -243,8 +558,8 @@ mod tests {
         let three = S::from_num(3);
         let four = S::from_num(4);
 
-        assert_eq!(pow::<S, D>(two, zero), Ok(one.into()));
-        assert_eq!(pow::<S, D>(zero, two), Ok(zero.into()));
+        assert_eq!(pow::<S, D>(two, zero), Ok(one));
+        assert_eq!(pow::<S, D>(zero, two), Ok(zero));
 
         let result: f64 = pow::<S, D>(two, three).unwrap().lossy_into();
         assert_relative_eq!(result, 8.0, epsilon = 1.0e-6);
@@ -252,19 +567,75 @@ mod tests {
         let result: f64 = pow::<S, D>(one / four, two).unwrap().lossy_into();
         assert_relative_eq!(result, 0.0625, epsilon = 1.0e-6);
 
-        assert_eq!(pow::<S, D>(two, one), Ok(two.into()));
+        assert_eq!(pow::<S, D>(two, one), Ok(two));
 
         let result: f64 = pow::<S, D>(one / four, one / two).unwrap().lossy_into();
         assert_relative_eq!(result, 0.5, epsilon = 1.0e-6);
 
         assert_eq!(
             pow(S::from_num(22.1234), S::from_num(2.1)),
-            Ok(D::from_num(667.097035126091))
+            Ok(D::from_str("667.096912177180318263195492394612602").unwrap())
         );
 
         assert_eq!(
             pow(S::from_num(0.986069911074), S::from_num(1.541748732743)),
-            Ok(D::from_num(0.978604513883))
+            Ok(D::from_str("0.9786045144748965359268284570515045").unwrap())
         );
     }
+
+    #[test]
+    fn pow_with_result_overflowing_integer_range_is_err_not_silently_wrong() {
+        // Regression test: `pow`'s own `overflowing_to_num::<D>()` check is a same-type no-op
+        // and never catches this, so it relies entirely on `exp2`'s shift being verified.
+        // `FixedBalance` (the crate's default `S`/`D`) has only 18 integer bits (max ~262143),
+        // but 3^12 == 531441.
+        type S = FixedBalance;
+        type D = FixedBalance;
+
+        assert_eq!(pow::<S, D>(S::from_num(3), S::from_num(12)), Err(()));
+    }
+
+    #[test]
+    fn sqrt_works() {
+        type S = U64F64;
+        type D = U64F64;
+
+        assert_eq!(sqrt::<S, D>(S::from_num(0)), Ok(D::from_num(0)));
+        assert_eq!(sqrt::<S, D>(S::from_num(1)), Ok(D::from_num(1)));
+        assert_eq!(sqrt::<S, D>(S::from_num(4)), Ok(D::from_num(2)));
+        assert_eq!(sqrt::<S, D>(S::from_num(1_000_000)), Ok(D::from_num(1_000)));
+
+        let result: f64 = sqrt::<S, D>(S::from_num(2)).unwrap().lossy_into();
+        assert_relative_eq!(result, core::f64::consts::SQRT_2, epsilon = 1.0e-18);
+    }
+
+    #[test]
+    fn nth_root_works() {
+        type S = U64F64;
+        type D = U64F64;
+
+        assert_eq!(nth_root::<S, D>(S::from_num(0), 3), Ok(D::from_num(0)));
+        assert_eq!(nth_root::<S, D>(S::from_num(1), 5), Ok(D::from_num(1)));
+        assert_eq!(nth_root::<S, D>(S::from_num(8), 1), Ok(D::from_num(8)));
+        assert_eq!(nth_root::<S, D>(S::from_num(8), 3), Ok(D::from_num(2)));
+        assert_eq!(nth_root::<S, D>(S::from_num(0), 0), Err(()));
+
+        let result: f64 = nth_root::<S, D>(S::from_num(27), 3).unwrap().lossy_into();
+        assert_relative_eq!(result, 3.0, epsilon = 1.0e-12);
+    }
+
+    #[test]
+    fn nth_root_uses_shift_checked_exp2() {
+        // Regression coverage: `nth_root` computes `exp2(log2(x)/n)`, so it shares chunk0-2's
+        // shift-verification fix rather than being exempt from it. Confirms a large-operand root
+        // still lands on the correct value instead of a silently wrapped one.
+        type S = U64F64;
+        type D = U64F64;
+
+        let result: f64 = nth_root::<S, D>(S::from_num(1_000_000_000_000u64), 4)
+            .unwrap()
+            .lossy_into();
+        assert_relative_eq!(result, 1000.0, epsilon = 1.0e-6);
+    }
 }
+
@@ -0,0 +1,8 @@
+use fixed::types::extra::U110;
+use fixed::FixedU128;
+
+/// Generic balance type used for reserves, amounts and weights across this crate.
+pub type Balance = u128;
+
+/// Fixed-point type used as the default `S`/`D` parameter for the transcendental helpers.
+pub type FixedBalance = FixedU128<U110>;